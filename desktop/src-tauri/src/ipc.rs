@@ -0,0 +1,72 @@
+//! Local control channel so external tools (a CLI, a shell hotkey, a script) can drive an
+//! already-running launcher without going through the webview: a Unix domain socket on Unix,
+//! a named pipe on Windows. Accepts `start` / `stop` / `status` / `health`, either as a bare
+//! line or as `{"cmd": "..."}` JSON, and maps them onto the existing Tauri commands.
+
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+
+use crate::settings::SettingsStore;
+use crate::GhostStreamState;
+
+#[cfg(unix)]
+#[path = "ipc_unix.rs"]
+mod platform;
+
+#[cfg(windows)]
+#[path = "ipc_windows.rs"]
+mod platform;
+
+/// Removes any stale socket/pipe left by a previous run, binds the control channel, and spawns
+/// the accept loop in the background. Returns an `Err` instead of binding if another launcher
+/// instance is already live and answering on the channel.
+pub fn start_ipc_server(app: AppHandle) -> Result<(), String> {
+    platform::start_ipc_server(app)
+}
+
+/// Parses one line of input as either `{"cmd": "..."}` JSON or a bare command word, runs it
+/// against the existing GhostStream commands, and returns a single JSON response line.
+fn dispatch(app: &AppHandle, line: &str) -> String {
+    let command = parse_command(line);
+
+    let response = match command.as_str() {
+        "start" => {
+            let state = app.state::<GhostStreamState>();
+            let settings_store = app.state::<SettingsStore>();
+            match crate::start_ghoststream(app.clone(), state, settings_store) {
+                Ok(()) => json!({"ok": true, "result": "started"}),
+                Err(e) => json!({"ok": false, "error": e}),
+            }
+        }
+        "stop" => {
+            let state = app.state::<GhostStreamState>();
+            match crate::stop_ghoststream(state) {
+                Ok(()) => json!({"ok": true, "result": "stopped"}),
+                Err(e) => json!({"ok": false, "error": e}),
+            }
+        }
+        "status" => {
+            let state = app.state::<GhostStreamState>();
+            json!({"ok": true, "result": {"running": crate::is_ghoststream_running(state)}})
+        }
+        "health" => {
+            let settings_store = app.state::<SettingsStore>();
+            match crate::check_server_health(settings_store) {
+                Ok(body) => json!({"ok": true, "result": body}),
+                Err(e) => json!({"ok": false, "error": e}),
+            }
+        }
+        other => json!({"ok": false, "error": format!("unknown command: {}", other)}),
+    };
+
+    response.to_string()
+}
+
+fn parse_command(line: &str) -> String {
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(line) {
+        if let Some(cmd) = value.get("cmd").and_then(|v| v.as_str()) {
+            return cmd.trim().to_lowercase();
+        }
+    }
+    line.trim().to_lowercase()
+}
@@ -0,0 +1,65 @@
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+fn socket_path() -> PathBuf {
+    std::env::temp_dir().join("ghoststream-launcher.sock")
+}
+
+pub fn start_ipc_server(app: AppHandle) -> Result<(), String> {
+    let path = socket_path();
+
+    if path.exists() {
+        if another_instance_is_live(&path) {
+            return Err(format!(
+                "Another GhostStream launcher is already listening on {}",
+                path.display()
+            ));
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    let listener = UnixListener::bind(&path)
+        .map_err(|e| format!("Failed to bind control socket {}: {}", path.display(), e))?;
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let app = app.clone();
+            std::thread::spawn(move || handle_connection(app, stream));
+        }
+    });
+
+    Ok(())
+}
+
+/// Tries to connect to an existing socket and get a response to a `status` probe; if that
+/// succeeds, a live instance already owns the channel.
+fn another_instance_is_live(path: &Path) -> bool {
+    let Ok(mut stream) = UnixStream::connect(path) else {
+        return false;
+    };
+
+    let _ = stream.set_read_timeout(Some(Duration::from_millis(300)));
+    if writeln!(stream, "status").is_err() {
+        return false;
+    }
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line).unwrap_or(0) > 0
+}
+
+fn handle_connection(app: AppHandle, stream: UnixStream) {
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+
+    for line in BufReader::new(stream).lines().flatten() {
+        let response = super::dispatch(&app, &line);
+        if writeln!(writer, "{}", response).is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::ffi::OsStr;
+use std::io;
+use std::os::windows::ffi::OsStrExt;
+use std::time::Duration;
+
+use tauri::AppHandle;
+use windows_sys::Win32::Foundation::{CloseHandle, GetLastError, ERROR_PIPE_BUSY, HANDLE};
+use windows_sys::Win32::Storage::FileSystem::{
+    CreateFileW, ReadFile, WriteFile, FILE_GENERIC_READ, FILE_GENERIC_WRITE, OPEN_EXISTING,
+};
+use windows_sys::Win32::System::Pipes::{
+    ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, WaitNamedPipeW,
+    PIPE_ACCESS_DUPLEX, PIPE_READMODE_BYTE, PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+};
+
+const PIPE_NAME: &str = r"\\.\pipe\ghoststream-launcher";
+const BUFFER_SIZE: u32 = 4096;
+
+fn wide_pipe_name() -> Vec<u16> {
+    OsStr::new(PIPE_NAME)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect()
+}
+
+pub fn start_ipc_server(app: AppHandle) -> Result<(), String> {
+    if another_instance_is_live() {
+        return Err(format!(
+            "Another GhostStream launcher is already listening on {}",
+            PIPE_NAME
+        ));
+    }
+
+    std::thread::spawn(move || loop {
+        match create_pipe_instance() {
+            Ok(handle) => {
+                let connected = unsafe { ConnectNamedPipe(handle, std::ptr::null_mut()) } != 0
+                    || unsafe { GetLastError() } == 535; // ERROR_PIPE_CONNECTED
+                if connected {
+                    let app = app.clone();
+                    std::thread::spawn(move || {
+                        handle_connection(app, handle);
+                    });
+                } else {
+                    unsafe { CloseHandle(handle) };
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to create control pipe instance: {}", e);
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+fn create_pipe_instance() -> io::Result<HANDLE> {
+    let name = wide_pipe_name();
+    let handle = unsafe {
+        CreateNamedPipeW(
+            name.as_ptr(),
+            PIPE_ACCESS_DUPLEX,
+            PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT,
+            PIPE_UNLIMITED_INSTANCES,
+            BUFFER_SIZE,
+            BUFFER_SIZE,
+            0,
+            std::ptr::null_mut(),
+        )
+    };
+
+    if handle == windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(handle)
+    }
+}
+
+/// Tries to open the pipe as a client and get a response to a `status` probe; if that
+/// succeeds, a live instance already owns the channel. No pipe existing yet (the normal
+/// first launch) must NOT count as "live".
+fn another_instance_is_live() -> bool {
+    let name = wide_pipe_name();
+
+    let waited = unsafe { WaitNamedPipeW(name.as_ptr(), 300) } != 0;
+    if !waited {
+        // ERROR_FILE_NOT_FOUND means no pipe exists yet (the normal first launch) and a
+        // wait timeout means we couldn't confirm anything either way — neither is "live".
+        // Only a pipe reported busy (another instance is holding every open slot) counts.
+        return unsafe { GetLastError() } == ERROR_PIPE_BUSY;
+    }
+
+    let handle = unsafe {
+        CreateFileW(
+            name.as_ptr(),
+            FILE_GENERIC_READ | FILE_GENERIC_WRITE,
+            0,
+            std::ptr::null_mut(),
+            OPEN_EXISTING,
+            0,
+            0,
+        )
+    };
+
+    if handle == windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE {
+        return false;
+    }
+
+    let request = b"status\n";
+    let mut written = 0u32;
+    let wrote_ok =
+        unsafe { WriteFile(handle, request.as_ptr(), request.len() as u32, &mut written, std::ptr::null_mut()) } != 0;
+
+    let mut buf = [0u8; 256];
+    let mut read = 0u32;
+    let read_ok = wrote_ok
+        && unsafe {
+            ReadFile(
+                handle,
+                buf.as_mut_ptr(),
+                buf.len() as u32,
+                &mut read,
+                std::ptr::null_mut(),
+            )
+        } != 0;
+
+    unsafe { CloseHandle(handle) };
+    read_ok && read > 0
+}
+
+fn handle_connection(app: AppHandle, handle: HANDLE) {
+    let mut buf = [0u8; BUFFER_SIZE as usize];
+
+    loop {
+        let mut read = 0u32;
+        let ok = unsafe {
+            ReadFile(
+                handle,
+                buf.as_mut_ptr(),
+                buf.len() as u32,
+                &mut read,
+                std::ptr::null_mut(),
+            )
+        } != 0;
+
+        if !ok || read == 0 {
+            break;
+        }
+
+        let line = String::from_utf8_lossy(&buf[..read as usize]);
+        let response = format!("{}\n", super::dispatch(&app, line.trim_end()));
+        let response_bytes = response.as_bytes();
+
+        let mut written = 0u32;
+        let wrote_ok = unsafe {
+            WriteFile(
+                handle,
+                response_bytes.as_ptr(),
+                response_bytes.len() as u32,
+                &mut written,
+                std::ptr::null_mut(),
+            )
+        } != 0;
+
+        if !wrote_ok {
+            break;
+        }
+    }
+
+    unsafe {
+        DisconnectNamedPipe(handle);
+        CloseHandle(handle);
+    }
+}
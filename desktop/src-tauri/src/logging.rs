@@ -0,0 +1,114 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader};
+use std::process::{ChildStderr, ChildStdout};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+/// Maximum number of lines retained in the ring buffer before the oldest are dropped.
+const LOG_BUFFER_CAPACITY: usize = 2000;
+
+/// A single line captured from the GhostStream child process.
+#[derive(Clone, Serialize)]
+pub struct LogLine {
+    pub source: &'static str,
+    pub level: String,
+    pub message: String,
+}
+
+/// Bounded ring buffer of recent log lines, shared across reader threads and commands.
+pub struct LogBuffer {
+    lines: Mutex<VecDeque<LogLine>>,
+}
+
+impl LogBuffer {
+    pub fn new() -> Self {
+        Self {
+            lines: Mutex::new(VecDeque::with_capacity(LOG_BUFFER_CAPACITY)),
+        }
+    }
+
+    fn push(&self, line: LogLine) {
+        let mut lines = self.lines.lock().unwrap();
+        if lines.len() >= LOG_BUFFER_CAPACITY {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    /// Snapshot the buffer's current contents, oldest first.
+    pub fn snapshot(&self) -> Vec<LogLine> {
+        self.lines.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+/// Parses a raw line as a `{level, message, ...}` JSON log record, falling back to treating
+/// the whole line as the message at "info" level when it isn't valid JSON.
+fn parse_log_line(source: &'static str, raw: &str) -> LogLine {
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(raw) {
+        let level = value
+            .get("level")
+            .and_then(|v| v.as_str())
+            .unwrap_or("info")
+            .to_string();
+        let message = value
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or(raw)
+            .to_string();
+        LogLine {
+            source,
+            level,
+            message,
+        }
+    } else {
+        LogLine {
+            source,
+            level: "info".to_string(),
+            message: raw.to_string(),
+        }
+    }
+}
+
+fn spawn_reader<R, F>(app: AppHandle, buffer: std::sync::Arc<LogBuffer>, reader: R, source: &'static str, mut next_line: F)
+where
+    R: std::io::Read + Send + 'static,
+    F: FnMut(&mut BufReader<R>) -> std::io::Result<Option<String>> + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        loop {
+            match next_line(&mut reader) {
+                Ok(Some(raw)) => {
+                    let line = parse_log_line(source, raw.trim_end());
+                    buffer.push(line.clone());
+                    let _ = app.emit_all("ghoststream-log", &line);
+                }
+                Ok(None) => break,
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+fn read_one_line<R: std::io::Read>(reader: &mut BufReader<R>) -> std::io::Result<Option<String>> {
+    let mut raw = String::new();
+    let bytes_read = reader.read_line(&mut raw)?;
+    if bytes_read == 0 {
+        Ok(None)
+    } else {
+        Ok(Some(raw))
+    }
+}
+
+/// Spawns the stdout/stderr reader threads for a freshly started GhostStream child process.
+pub fn capture_child_output(
+    app: AppHandle,
+    buffer: std::sync::Arc<LogBuffer>,
+    stdout: ChildStdout,
+    stderr: ChildStderr,
+) {
+    spawn_reader(app.clone(), buffer.clone(), stdout, "stdout", read_one_line);
+    spawn_reader(app, buffer, stderr, "stderr", read_one_line);
+}
@@ -4,60 +4,156 @@
 )]
 
 use std::net::UdpSocket;
-use std::process::{Child, Command};
-use std::sync::Mutex;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
 use tauri::{
-    CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu, SystemTrayMenuItem,
+    AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
+    SystemTrayMenuItem,
 };
 
+mod ipc;
+mod logging;
+mod port;
+mod settings;
+mod supervisor;
+
+use logging::LogBuffer;
+use port::PortOwner;
+use settings::{Settings, SettingsStore};
+use supervisor::{Supervisor, SupervisorSnapshot};
+
+/// Env var the spawned `ghoststream` process reads to learn which port to bind.
+const GHOSTSTREAM_PORT_ENV: &str = "GHOSTSTREAM_PORT";
+
 struct GhostStreamState {
-    process: Mutex<Option<Child>>,
+    process: Arc<Mutex<Option<Child>>>,
+    log_buffer: Arc<LogBuffer>,
+    supervisor: Arc<Supervisor>,
 }
 
-#[tauri::command]
-fn start_ghoststream(state: tauri::State<GhostStreamState>) -> Result<(), String> {
-    let mut process_guard = state.process.lock().map_err(|e| e.to_string())?;
-
-    if process_guard.is_some() {
-        return Err("GhostStream is already running".to_string());
+/// Spawns the `ghoststream` Python module with piped stdio. Uses `settings.python_command`
+/// when set, otherwise tries `python3` before falling back to `python` on non-Windows
+/// platforms. Used both for the initial launch and for supervisor-driven restarts.
+fn spawn_ghoststream_child(settings: &Settings) -> Result<Child, String> {
+    fn configure(command: &mut Command, port: u16) -> &mut Command {
+        command
+            .args(["-m", "ghoststream"])
+            .env(GHOSTSTREAM_PORT_ENV, port.to_string())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
     }
 
-    // Check if server is already running on port 8765
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_millis(500))
-        .build()
-        .map_err(|e| e.to_string())?;
-    
-    if client.get("http://localhost:8765/api/health").send().is_ok() {
-        return Err("GhostStream is already running on port 8765".to_string());
+    if let Some(python_command) = &settings.python_command {
+        return configure(&mut Command::new(python_command), settings.port)
+            .spawn()
+            .map_err(|e| format!("Failed to start GhostStream: {}", e));
     }
 
-    // Determine the command based on OS
     #[cfg(target_os = "windows")]
-    let child = Command::new("python")
-        .args(["-m", "ghoststream"])
+    let child = configure(&mut Command::new("python"), settings.port)
         .spawn()
         .map_err(|e| format!("Failed to start GhostStream: {}", e))?;
 
     #[cfg(not(target_os = "windows"))]
-    let child = Command::new("python3")
-        .args(["-m", "ghoststream"])
+    let child = configure(&mut Command::new("python3"), settings.port)
         .spawn()
-        .or_else(|_| {
-            Command::new("python")
-                .args(["-m", "ghoststream"])
-                .spawn()
-        })
+        .or_else(|_| configure(&mut Command::new("python"), settings.port).spawn())
         .map_err(|e| format!("Failed to start GhostStream: {}", e))?;
 
+    Ok(child)
+}
+
+#[tauri::command]
+fn start_ghoststream(
+    app: AppHandle,
+    state: tauri::State<GhostStreamState>,
+    settings_store: tauri::State<SettingsStore>,
+) -> Result<(), String> {
+    let mut process_guard = state.process.lock().map_err(|e| e.to_string())?;
+
+    if process_guard.is_some() {
+        return Err("GhostStream is already running".to_string());
+    }
+
+    let settings = settings_store.get();
+
+    // Check if server is already running on the configured port
+    let client = settings.http_client()?;
+
+    if client
+        .get(format!("{}/api/health", settings.base_url()))
+        .send()
+        .is_ok()
+    {
+        return Err(match port::find_port_owner(settings.port) {
+            Some(owner) => format!(
+                "Port {} is already in use by {} (PID {})",
+                settings.port, owner.process_name, owner.pid
+            ),
+            None => format!("GhostStream is already running on port {}", settings.port),
+        });
+    }
+
+    let mut child = spawn_ghoststream_child(&settings)?;
+
     println!("GhostStream process started with PID: {}", child.id());
 
+    let stdout = child.stdout.take().expect("child spawned with piped stdout");
+    let stderr = child.stderr.take().expect("child spawned with piped stderr");
+    logging::capture_child_output(app.clone(), state.log_buffer.clone(), stdout, stderr);
+
     *process_guard = Some(child);
+    drop(process_guard);
+
+    state.supervisor.begin();
+    supervisor::spawn_monitor(
+        app,
+        state.process.clone(),
+        state.log_buffer.clone(),
+        state.supervisor.clone(),
+    );
+
     Ok(())
 }
 
+#[tauri::command]
+fn get_recent_logs(state: tauri::State<GhostStreamState>) -> Vec<logging::LogLine> {
+    state.log_buffer.snapshot()
+}
+
+#[tauri::command]
+fn get_supervisor_state(state: tauri::State<GhostStreamState>) -> SupervisorSnapshot {
+    state.supervisor.snapshot()
+}
+
+/// Identifies whatever process is currently bound to the GhostStream port, so the UI can tell
+/// a stale orphan from another app or a legitimately running instance.
+#[tauri::command]
+fn get_port_owner(settings_store: tauri::State<SettingsStore>) -> Option<PortOwner> {
+    port::find_port_owner(settings_store.get().port)
+}
+
+/// Terminates a stale GhostStream orphan holding the port, after verifying it's still the
+/// same PID and looks like a `python`/`ghoststream` process.
+#[tauri::command]
+fn reclaim_port(pid: u32, settings_store: tauri::State<SettingsStore>) -> Result<(), String> {
+    port::reclaim_port(settings_store.get().port, pid)
+}
+
+#[tauri::command]
+fn get_settings(settings_store: tauri::State<SettingsStore>) -> Settings {
+    settings_store.get()
+}
+
+#[tauri::command]
+fn set_settings(new_settings: Settings, settings_store: tauri::State<SettingsStore>) -> Result<(), String> {
+    settings_store.set(new_settings)
+}
+
 #[tauri::command]
 fn stop_ghoststream(state: tauri::State<GhostStreamState>) -> Result<(), String> {
+    state.supervisor.request_stop();
+
     let mut process_guard = state.process.lock().map_err(|e| e.to_string())?;
 
     if let Some(mut child) = process_guard.take() {
@@ -106,18 +202,16 @@ fn is_ghosthub_network() -> bool {
 }
 
 #[tauri::command]
-fn check_server_health() -> Result<String, String> {
+fn check_server_health(settings_store: tauri::State<SettingsStore>) -> Result<String, String> {
     // Check if GhostStream server is responding
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(2))
-        .build()
-        .map_err(|e| e.to_string())?;
-    
+    let settings = settings_store.get();
+    let client = settings.http_client()?;
+
     let res = client
-        .get("http://localhost:8765/api/health")
+        .get(format!("{}/api/health", settings.base_url()))
         .send()
         .map_err(|e| format!("Server not responding: {}", e))?;
-    
+
     if res.status().is_success() {
         let body = res.text().map_err(|e| e.to_string())?;
         Ok(body)
@@ -127,17 +221,15 @@ fn check_server_health() -> Result<String, String> {
 }
 
 #[tauri::command]
-fn get_capabilities() -> Result<String, String> {
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(2))
-        .build()
-        .map_err(|e| e.to_string())?;
-    
+fn get_capabilities(settings_store: tauri::State<SettingsStore>) -> Result<String, String> {
+    let settings = settings_store.get();
+    let client = settings.http_client()?;
+
     let res = client
-        .get("http://localhost:8765/api/capabilities")
+        .get(format!("{}/api/capabilities", settings.base_url()))
         .send()
         .map_err(|e| format!("Failed to get capabilities: {}", e))?;
-    
+
     if res.status().is_success() {
         let body = res.text().map_err(|e| e.to_string())?;
         Ok(body)
@@ -146,30 +238,103 @@ fn get_capabilities() -> Result<String, String> {
     }
 }
 
+/// Capabilities the desktop app requires the running server to advertise before a session
+/// is allowed to proceed.
+const REQUIRED_CAPABILITIES: &[&str] = &["transcode", "hls", "range_requests"];
+
+/// Pulls the list of advertised capability names out of `/api/capabilities`, accepting either
+/// shape seen in the wild: a bare array of names, `{"capabilities": [...]}`, or an
+/// object mapping each capability name to a boolean (only the truthy ones count).
+fn extract_capability_names(value: &serde_json::Value) -> Vec<String> {
+    fn names_from_array(arr: &[serde_json::Value]) -> Vec<String> {
+        arr.iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect()
+    }
+
+    if let Some(arr) = value.as_array() {
+        return names_from_array(arr);
+    }
+
+    if let Some(arr) = value.get("capabilities").and_then(|c| c.as_array()) {
+        return names_from_array(arr);
+    }
+
+    if let Some(obj) = value.as_object() {
+        return obj
+            .iter()
+            .filter(|(_, v)| v.as_bool().unwrap_or(false))
+            .map(|(k, _)| k.clone())
+            .collect();
+    }
+
+    Vec::new()
+}
+
+/// Fetches `/api/capabilities` and confirms every entry in `REQUIRED_CAPABILITIES` is present,
+/// returning an `Err` naming whatever is missing.
+fn verify_required_capabilities(client: &reqwest::blocking::Client, base_url: &str) -> Result<(), String> {
+    let res = client
+        .get(format!("{}/api/capabilities", base_url))
+        .send()
+        .map_err(|e| format!("Failed to fetch capabilities: {}", e))?;
+
+    if !res.status().is_success() {
+        return Err(format!(
+            "Server returned status {} for capabilities",
+            res.status()
+        ));
+    }
+
+    let body = res.text().map_err(|e| e.to_string())?;
+    let value: serde_json::Value =
+        serde_json::from_str(&body).map_err(|e| format!("Invalid capabilities response: {}", e))?;
+
+    let advertised = extract_capability_names(&value);
+
+    let missing: Vec<&str> = REQUIRED_CAPABILITIES
+        .iter()
+        .filter(|required| !advertised.iter().any(|a| a == *required))
+        .copied()
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Server is missing required capabilities: {}",
+            missing.join(", ")
+        ))
+    }
+}
+
 #[tauri::command]
-fn wait_for_server_ready() -> Result<String, String> {
-    // Check every 200ms for faster detection, up to 20 seconds
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_millis(200))
-        .build()
-        .map_err(|e| e.to_string())?;
-    
+fn wait_for_server_ready(settings_store: tauri::State<SettingsStore>) -> Result<String, String> {
+    let settings = settings_store.get();
+    let base_url = settings.base_url();
+
+    // Poll every 200ms for faster detection, up to 20 seconds; each request itself honors
+    // the configured network timeout (0 meaning wait indefinitely for a single response).
+    let client = settings.http_client()?;
+
     // Check immediately first
-    if let Ok(res) = client.get("http://localhost:8765/api/health").send() {
+    if let Ok(res) = client.get(format!("{}/api/health", base_url)).send() {
         if res.status().is_success() {
             let body = res.text().unwrap_or_default();
+            verify_required_capabilities(&client, &base_url)?;
             println!("Server ready immediately");
             return Ok(body);
         }
     }
-    
+
     // Then poll every 200ms
     for i in 0..100 {
         std::thread::sleep(std::time::Duration::from_millis(200));
-        
-        match client.get("http://localhost:8765/api/health").send() {
+
+        match client.get(format!("{}/api/health", base_url)).send() {
             Ok(res) if res.status().is_success() => {
                 let body = res.text().unwrap_or_default();
+                verify_required_capabilities(&client, &base_url)?;
                 let secs = (i + 1) as f32 * 0.2;
                 println!("Server ready after {:.1} seconds", secs);
                 return Ok(body);
@@ -182,7 +347,7 @@ fn wait_for_server_ready() -> Result<String, String> {
             }
         }
     }
-    
+
     Err("Server failed to start within 20 seconds".to_string())
 }
 
@@ -206,7 +371,19 @@ fn main() {
 
     tauri::Builder::default()
         .manage(GhostStreamState {
-            process: Mutex::new(None),
+            process: Arc::new(Mutex::new(None)),
+            log_buffer: Arc::new(LogBuffer::new()),
+            supervisor: Arc::new(Supervisor::new()),
+        })
+        .setup(|app| {
+            app.manage(SettingsStore::load(&app.handle()));
+
+            if let Err(e) = ipc::start_ipc_server(app.handle()) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+
+            Ok(())
         })
         .system_tray(tray)
         .on_system_tray_event(|app, event| match event {
@@ -226,7 +403,8 @@ fn main() {
                         }
                     }
                     "start" => {
-                        let _ = start_ghoststream(state);
+                        let settings_store = app.state::<SettingsStore>();
+                        let _ = start_ghoststream(app.clone(), state, settings_store);
                     }
                     "stop" => {
                         let _ = stop_ghoststream(state);
@@ -256,7 +434,13 @@ fn main() {
             is_ghosthub_network,
             check_server_health,
             get_capabilities,
-            wait_for_server_ready
+            wait_for_server_ready,
+            get_recent_logs,
+            get_supervisor_state,
+            get_port_owner,
+            reclaim_port,
+            get_settings,
+            set_settings
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
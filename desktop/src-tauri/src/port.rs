@@ -0,0 +1,201 @@
+use serde::Serialize;
+
+/// Identifies whatever process currently owns a listening TCP port.
+#[derive(Clone, Serialize)]
+pub struct PortOwner {
+    pub pid: u32,
+    pub process_name: String,
+}
+
+/// Resolves the PID (and process name, where available) listening on `port` on any local
+/// address, so the UI can tell a stale orphan from another app or a legitimate instance.
+pub fn find_port_owner(port: u16) -> Option<PortOwner> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::find_port_owner(port)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        unix::find_port_owner(port)
+    }
+}
+
+/// Terminates the process bound to `port`, but only if its name looks like a GhostStream
+/// orphan (`python`, `python3`, or `ghoststream`) — refuses to kill anything else.
+pub fn reclaim_port(port: u16, expected_pid: u32) -> Result<(), String> {
+    let owner = find_port_owner(port)
+        .ok_or_else(|| format!("No process found holding port {}", port))?;
+
+    if owner.pid != expected_pid {
+        return Err(format!(
+            "Port {} is now held by PID {} (expected {}); refusing to act on stale info",
+            port, owner.pid, expected_pid
+        ));
+    }
+
+    let name_lower = owner.process_name.to_lowercase();
+    if !(name_lower.contains("python") || name_lower.contains("ghoststream")) {
+        return Err(format!(
+            "Refusing to kill PID {} ({}): does not look like a GhostStream process",
+            owner.pid, owner.process_name
+        ));
+    }
+
+    kill_pid(owner.pid)
+}
+
+#[cfg(target_os = "windows")]
+fn kill_pid(pid: u32) -> Result<(), String> {
+    std::process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .status()
+        .map_err(|e| e.to_string())
+        .and_then(|status| {
+            if status.success() {
+                Ok(())
+            } else {
+                Err(format!("taskkill exited with status {}", status))
+            }
+        })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn kill_pid(pid: u32) -> Result<(), String> {
+    let result = unsafe { libc::kill(pid as i32, libc::SIGTERM) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error().to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use super::PortOwner;
+
+    /// Shells out to `netstat`, since parsing the raw `GetExtendedTcpTable` output requires
+    /// linking additional Win32 FFI bindings this crate doesn't otherwise need.
+    pub fn find_port_owner(port: u16) -> Option<PortOwner> {
+        let output = std::process::Command::new("netstat")
+            .args(["-ano", "-p", "TCP"])
+            .output()
+            .ok()?;
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        let needle = format!(":{}", port);
+
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 5 || fields[0] != "TCP" {
+                continue;
+            }
+            if !fields[1].ends_with(&needle) || fields[3] != "LISTENING" {
+                continue;
+            }
+            if let Ok(pid) = fields[4].parse::<u32>() {
+                let process_name = process_name_for_pid(pid).unwrap_or_else(|| "unknown".into());
+                return Some(PortOwner { pid, process_name });
+            }
+        }
+
+        None
+    }
+
+    fn process_name_for_pid(pid: u32) -> Option<String> {
+        let output = std::process::Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+            .output()
+            .ok()?;
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        let first_field = text.split(',').next()?;
+        Some(first_field.trim_matches('"').to_string())
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod unix {
+    use super::PortOwner;
+    use std::fs;
+
+    /// Parses `/proc/net/tcp` and `/proc/net/tcp6` for a socket bound to `port` in the
+    /// `LISTEN` state, then walks every process's `/proc/<pid>/fd` entries looking for the
+    /// matching socket inode.
+    pub fn find_port_owner(port: u16) -> Option<PortOwner> {
+        let inode = listening_inode_for_port(port)?;
+        let pid = pid_owning_inode(inode)?;
+        let process_name = fs::read_to_string(format!("/proc/{}/comm", pid))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|_| "unknown".to_string());
+
+        Some(PortOwner { pid, process_name })
+    }
+
+    const TCP_LISTEN_STATE: &str = "0A";
+    /// `/proc/net/tcp` covers IPv4 listeners; `/proc/net/tcp6` covers IPv6 ones (e.g. a
+    /// server bound to `::` or `::1`) — a listener can live in either depending on how the
+    /// Python server binds the socket.
+    const TCP_TABLES: &[&str] = &["/proc/net/tcp", "/proc/net/tcp6"];
+
+    fn listening_inode_for_port(port: u16) -> Option<u64> {
+        let port_hex = format!("{:04X}", port);
+
+        for table in TCP_TABLES {
+            let Ok(contents) = fs::read_to_string(table) else {
+                continue;
+            };
+
+            for line in contents.lines().skip(1) {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() < 10 {
+                    continue;
+                }
+
+                let local_address = fields[1];
+                let state = fields[3];
+                let inode_field = fields[9];
+
+                if state != TCP_LISTEN_STATE {
+                    continue;
+                }
+
+                if let Some((_, local_port)) = local_address.split_once(':') {
+                    if local_port.eq_ignore_ascii_case(&port_hex) {
+                        if let Ok(inode) = inode_field.parse::<u64>() {
+                            return Some(inode);
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn pid_owning_inode(target_inode: u64) -> Option<u32> {
+        let needle = format!("socket:[{}]", target_inode);
+
+        for entry in fs::read_dir("/proc").ok()?.flatten() {
+            let pid: u32 = match entry.file_name().to_str().and_then(|s| s.parse().ok()) {
+                Some(pid) => pid,
+                None => continue,
+            };
+
+            let fd_dir = entry.path().join("fd");
+            let Ok(fds) = fs::read_dir(&fd_dir) else {
+                continue;
+            };
+
+            for fd in fds.flatten() {
+                if let Ok(link) = fs::read_link(fd.path()) {
+                    if link.to_string_lossy() == needle {
+                        return Some(pid);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
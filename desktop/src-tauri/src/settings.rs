@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// User-configurable launcher settings, persisted as JSON under the app data dir.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Port the GhostStream server listens on and the desktop app connects to.
+    pub port: u16,
+    /// Interpreter command/path to launch, e.g. `python3` or `C:\Python311\python.exe`.
+    /// `None` falls back to the platform-default lookup (`python` on Windows, `python3` then
+    /// `python` elsewhere).
+    pub python_command: Option<String>,
+    /// Network request timeout in milliseconds. `0` means wait indefinitely.
+    pub timeout_ms: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            port: 8765,
+            python_command: None,
+            timeout_ms: 2000,
+        }
+    }
+}
+
+impl Settings {
+    /// Client request timeout, or `None` (no timeout / wait indefinitely) when `timeout_ms == 0`.
+    pub fn timeout(&self) -> Option<Duration> {
+        if self.timeout_ms == 0 {
+            None
+        } else {
+            Some(Duration::from_millis(self.timeout_ms))
+        }
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://localhost:{}", self.port)
+    }
+
+    /// Builds a `reqwest` blocking client honoring the configured timeout.
+    pub fn http_client(&self) -> Result<reqwest::blocking::Client, String> {
+        let mut builder = reqwest::blocking::Client::builder();
+        if let Some(timeout) = self.timeout() {
+            builder = builder.timeout(timeout);
+        }
+        builder.build().map_err(|e| e.to_string())
+    }
+}
+
+/// Holds the settings currently in effect and persists changes to disk.
+pub struct SettingsStore {
+    path: PathBuf,
+    current: RwLock<Settings>,
+}
+
+impl SettingsStore {
+    /// Loads settings from the app data dir, falling back to defaults if the file is
+    /// missing or unreadable.
+    pub fn load(app: &AppHandle) -> Self {
+        let path = settings_path(app);
+        let current = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            path,
+            current: RwLock::new(current),
+        }
+    }
+
+    pub fn get(&self) -> Settings {
+        self.current.read().unwrap().clone()
+    }
+
+    pub fn set(&self, settings: Settings) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+
+        let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+        fs::write(&self.path, json).map_err(|e| e.to_string())?;
+
+        *self.current.write().unwrap() = settings;
+        Ok(())
+    }
+}
+
+fn settings_path(app: &AppHandle) -> PathBuf {
+    app.path_resolver()
+        .app_data_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(SETTINGS_FILE_NAME)
+}
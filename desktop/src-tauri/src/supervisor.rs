@@ -0,0 +1,223 @@
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::logging::LogBuffer;
+use crate::settings::{Settings, SettingsStore};
+
+/// Initial delay before the first restart attempt.
+const INITIAL_BACKOFF_MS: u64 = 500;
+/// Backoff is doubled after every failed attempt up to this cap.
+const MAX_BACKOFF_MS: u64 = 30_000;
+/// Give up and report a crash after this many consecutive failed restart attempts.
+const MAX_RESTART_ATTEMPTS: u32 = 10;
+/// How often the monitor thread polls the child's liveness.
+const POLL_INTERVAL_MS: u64 = 1000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SupervisorStatus {
+    Running,
+    Restarting,
+    Failed,
+    Stopped,
+}
+
+#[derive(Clone, Serialize)]
+pub struct SupervisorSnapshot {
+    pub status: SupervisorStatus,
+    pub attempt: u32,
+}
+
+/// Tracks whether the GhostStream child is expected to be running and how many consecutive
+/// restart attempts the monitor thread has made.
+pub struct Supervisor {
+    stop_requested: AtomicBool,
+    status: Mutex<SupervisorStatus>,
+    attempt: AtomicU32,
+}
+
+impl Supervisor {
+    pub fn new() -> Self {
+        Self {
+            stop_requested: AtomicBool::new(true),
+            status: Mutex::new(SupervisorStatus::Stopped),
+            attempt: AtomicU32::new(0),
+        }
+    }
+
+    pub fn snapshot(&self) -> SupervisorSnapshot {
+        SupervisorSnapshot {
+            status: *self.status.lock().unwrap(),
+            attempt: self.attempt.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Called by `start_ghoststream` once the initial child has been spawned, beginning a
+    /// fresh supervised session: unconditionally clears any prior stop request.
+    pub fn begin(&self) {
+        self.stop_requested.store(false, Ordering::SeqCst);
+        self.attempt.store(0, Ordering::SeqCst);
+        *self.status.lock().unwrap() = SupervisorStatus::Running;
+    }
+
+    /// Called after a restart has respawned the child and confirmed it's healthy. A no-op
+    /// if a stop was requested in the meantime (e.g. the user stopped the server while the
+    /// restart was still completing its health check) — otherwise this would clobber the
+    /// stop the user just asked for and leave `get_supervisor_state` reporting `Running`.
+    pub fn mark_running(&self) {
+        if self.stop_was_requested() {
+            return;
+        }
+
+        self.attempt.store(0, Ordering::SeqCst);
+        *self.status.lock().unwrap() = SupervisorStatus::Running;
+    }
+
+    /// Disables automatic restarts, e.g. because the user explicitly called `stop_ghoststream`.
+    pub fn request_stop(&self) {
+        self.stop_requested.store(true, Ordering::SeqCst);
+        *self.status.lock().unwrap() = SupervisorStatus::Stopped;
+    }
+
+    fn stop_was_requested(&self) -> bool {
+        self.stop_requested.load(Ordering::SeqCst)
+    }
+}
+
+/// Spawns a background thread that periodically checks whether the GhostStream child is still
+/// alive, and if it exits without a stop having been requested, respawns it with exponential
+/// backoff. Gives up and emits `ghoststream-crashed` after `MAX_RESTART_ATTEMPTS` failures.
+pub fn spawn_monitor(
+    app: AppHandle,
+    process: Arc<Mutex<Option<Child>>>,
+    log_buffer: Arc<LogBuffer>,
+    supervisor: Arc<Supervisor>,
+) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+
+        if supervisor.stop_was_requested() {
+            break;
+        }
+
+        let exited = {
+            let mut guard = process.lock().unwrap();
+            match guard.as_mut() {
+                Some(child) => match child.try_wait() {
+                    Ok(Some(_status)) => {
+                        *guard = None;
+                        true
+                    }
+                    Ok(None) => false,
+                    Err(_) => false,
+                },
+                None => break,
+            }
+        };
+
+        if !exited {
+            continue;
+        }
+
+        if supervisor.stop_was_requested() {
+            break;
+        }
+
+        println!("GhostStream exited unexpectedly, attempting to restart");
+        if !restart_with_backoff(&app, &process, &log_buffer, &supervisor) {
+            let _ = app.emit_all("ghoststream-crashed", ());
+            break;
+        }
+    });
+}
+
+/// Repeatedly attempts to respawn the child with exponential backoff, resetting the backoff
+/// once a health check confirms the new process came up. Returns `false` once
+/// `MAX_RESTART_ATTEMPTS` consecutive failures have been made.
+fn restart_with_backoff(
+    app: &AppHandle,
+    process: &Arc<Mutex<Option<Child>>>,
+    log_buffer: &Arc<LogBuffer>,
+    supervisor: &Arc<Supervisor>,
+) -> bool {
+    let mut backoff_ms = INITIAL_BACKOFF_MS;
+
+    for attempt in 1..=MAX_RESTART_ATTEMPTS {
+        if supervisor.stop_was_requested() {
+            return true;
+        }
+
+        *supervisor.status.lock().unwrap() = SupervisorStatus::Restarting;
+        supervisor.attempt.store(attempt, Ordering::SeqCst);
+
+        std::thread::sleep(Duration::from_millis(backoff_ms));
+
+        let settings = app.state::<SettingsStore>().get();
+
+        match crate::spawn_ghoststream_child(&settings) {
+            Ok(mut child) => {
+                if let (Some(stdout), Some(stderr)) = (child.stdout.take(), child.stderr.take()) {
+                    crate::logging::capture_child_output(
+                        app.clone(),
+                        log_buffer.clone(),
+                        stdout,
+                        stderr,
+                    );
+                }
+
+                {
+                    let mut guard = process.lock().unwrap();
+                    // A stop may have been requested while we were spawning; `stop_ghoststream`
+                    // can't kill a child it never saw (it found `process` empty while the
+                    // monitor was mid-restart), so if that happened we must kill this freshly
+                    // spawned one ourselves instead of handing it back to the supervisor.
+                    if supervisor.stop_was_requested() {
+                        drop(guard);
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        return true;
+                    }
+                    *guard = Some(child);
+                }
+
+                if wait_for_health_once(&settings) {
+                    println!("GhostStream restarted successfully after {} attempt(s)", attempt);
+                    supervisor.mark_running();
+                    return true;
+                }
+
+                println!("Restarted GhostStream but it failed its health check");
+            }
+            Err(e) => {
+                println!("Failed to restart GhostStream (attempt {}): {}", attempt, e);
+            }
+        }
+
+        backoff_ms = (backoff_ms * 2).min(MAX_BACKOFF_MS);
+    }
+
+    *supervisor.status.lock().unwrap() = SupervisorStatus::Failed;
+    false
+}
+
+/// A single best-effort health probe used to confirm a restarted child actually came up,
+/// separate from `wait_for_server_ready`'s longer polling loop.
+fn wait_for_health_once(settings: &Settings) -> bool {
+    std::thread::sleep(Duration::from_millis(500));
+
+    let client = match settings.http_client() {
+        Ok(client) => client,
+        Err(_) => return false,
+    };
+
+    client
+        .get(format!("{}/api/health", settings.base_url()))
+        .send()
+        .map(|res| res.status().is_success())
+        .unwrap_or(false)
+}